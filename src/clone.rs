@@ -0,0 +1,255 @@
+use crate::forge::ForgeKind;
+use crate::path::resolve_clone_directory;
+use crate::search;
+use clap::Parser;
+use color_eyre::{
+    Result,
+    eyre::{Context, eyre},
+};
+use regex::Regex;
+use std::{convert::Infallible, io::IsTerminal, path::PathBuf, str::FromStr};
+use tokio::process::Command;
+
+#[derive(Parser)]
+/// Clone a repository from GitHub, GitLab, Gitea/Forgejo, or Bitbucket and initialize it as a Jujutsu repo
+pub struct CloneCommand {
+    #[arg(value_name = "REPOSITORY")]
+    /// Repository to clone
+    ///
+    /// Uses the same syntax as `gh repo clone`, e.g. `owner/repo`, a full URL,
+    /// or `forge:owner/repo` to select a different forge (`gitlab:owner/repo`,
+    /// `codeberg:owner/repo`). If omitted, an interactive fuzzy picker over
+    /// your GitHub repositories is shown (requires a terminal)
+    repo: Option<String>,
+
+    #[arg()]
+    /// Directory into which to clone the repository
+    ///
+    /// By default, a new directory will be created in CWD with the name of the repo,
+    /// or under `--root` if that is set
+    directory: Option<PathBuf>,
+
+    #[arg(long)]
+    /// Colocate the repository (place `.git` in the root of the repository)
+    colocate: bool,
+
+    #[arg(short, long, default_value = "upstream")]
+    /// Upstream remote name when cloning a fork
+    upstream_remote_name: String,
+
+    #[arg(long)]
+    /// Forge to clone from, when it can't be inferred from `repo`
+    ///
+    /// Defaults to GitHub. Can also be selected with a forge prefix on
+    /// `repo`, e.g. `gitlab:owner/repo` or `codeberg:owner/repo` (conflicts
+    /// with a prefix already present on `repo`)
+    forge: Option<ForgeKind>,
+
+    #[arg(long)]
+    /// Host of a self-hosted GitLab, Gitea, or Forgejo instance
+    ///
+    /// Required for Gitea/Forgejo unless `repo` uses a host prefix like
+    /// `codeberg:owner/repo`. GitLab defaults to `gitlab.com`; ignored for
+    /// GitHub and Bitbucket
+    host: Option<String>,
+
+    #[arg(long, env = "GH_JJ_ROOT")]
+    /// Root directory under which to organize clones as `<root>/<host>/<owner>/<repo>`
+    ///
+    /// Ignored if `directory` is given
+    root: Option<PathBuf>,
+
+    #[arg(long)]
+    /// If the destination directory already exists, skip cloning instead of erroring
+    skip_if_exists: bool,
+
+    #[arg(allow_hyphen_values = true, num_args = 0.., last = true)]
+    /// Arguments to pass to `jj git clone`
+    rest: Vec<String>,
+}
+
+enum Source {
+    Forge {
+        kind: ForgeKind,
+        host: Option<&'static str>,
+        owner: Option<String>,
+        repo: String,
+        /// Whether `kind`/`host` came from an explicit `forge:owner/repo`
+        /// prefix, as opposed to defaulting to GitHub
+        explicit: bool,
+    },
+    Web(String),
+}
+
+impl FromStr for Source {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let prefixed = Regex::new(r"^([a-zA-Z0-9_-]+):(?:([a-zA-Z0-9-]+)\/)?([a-zA-Z0-9_.-]+)$").unwrap();
+        if let Some(captures) = prefixed.captures(s) {
+            if let Some((kind, host)) = ForgeKind::from_prefix(captures.get(1).unwrap().as_str()) {
+                return Ok(Source::Forge {
+                    kind,
+                    host,
+                    owner: captures.get(2).map(|m| m.as_str().to_string()),
+                    repo: captures.get(3).unwrap().as_str().to_string(),
+                    explicit: true,
+                });
+            }
+        }
+
+        let regex = Regex::new(r"^(?:([a-zA-Z0-9-]+)\/)?([a-zA-Z0-9_.-]+)$").unwrap();
+        Ok(if let Some(captures) = regex.captures(s) {
+            Source::Forge {
+                kind: ForgeKind::GitHub,
+                host: None,
+                owner: captures.get(1).map(|m| m.as_str().to_string()),
+                repo: captures.get(2).unwrap().as_str().to_string(),
+                explicit: false,
+            }
+        } else {
+            Source::Web(s.to_string())
+        })
+    }
+}
+
+pub async fn clone(cmd: CloneCommand) -> Result<()> {
+    let repo = match cmd.repo {
+        Some(repo) => repo,
+        None => {
+            if !std::io::stdin().is_terminal() || !std::io::stdout().is_terminal() {
+                return Err(eyre!(
+                    "A repository argument is required when not running in a terminal"
+                ));
+            }
+            search::pick_repo().await?
+        }
+    };
+
+    let source: Source = repo.parse().unwrap();
+    let mut upstream_url = None;
+
+    let repo_url = match source {
+        Source::Forge {
+            kind,
+            host,
+            owner,
+            repo: repo_name,
+            explicit,
+        } => {
+            if explicit && (cmd.forge.is_some() || cmd.host.is_some()) {
+                return Err(eyre!(
+                    "--forge/--host conflict with the forge prefix on REPOSITORY; use one or the other"
+                ));
+            }
+
+            let kind = cmd.forge.unwrap_or(kind);
+            let host = host.map(str::to_string).or(cmd.host);
+            let forge = kind.build(host.as_deref())?;
+
+            let owner = match owner {
+                Some(owner) => owner,
+                None => forge.default_owner().await?,
+            };
+
+            upstream_url = forge.parent(&owner, &repo_name).await?;
+
+            forge.clone_url(&owner, &repo_name)
+        }
+        Source::Web(url) => url,
+    };
+
+    let directory = resolve_clone_directory(cmd.directory, cmd.root.as_deref(), &repo_url)?;
+
+    run_clone(ClonePlan {
+        repo_url,
+        upstream_url,
+        directory,
+        colocate: cmd.colocate,
+        upstream_remote_name: cmd.upstream_remote_name,
+        skip_if_exists: cmd.skip_if_exists,
+        rest: cmd.rest,
+    })
+    .await
+}
+
+/// Everything needed to run `jj git clone` and, if the source is a fork,
+/// wire up the upstream remote afterwards.
+///
+/// Shared between the `clone` and `fork` subcommands so they agree on how
+/// the destination directory is resolved and the upstream remote is added.
+/// `directory` is always the actual destination the clone will end up at;
+/// it's never inferred from jj's output.
+pub struct ClonePlan {
+    pub repo_url: String,
+    pub upstream_url: Option<String>,
+    pub directory: PathBuf,
+    pub colocate: bool,
+    pub upstream_remote_name: String,
+    pub skip_if_exists: bool,
+    pub rest: Vec<String>,
+}
+
+pub async fn run_clone(plan: ClonePlan) -> Result<()> {
+    let already_existed = plan.directory.exists();
+
+    if already_existed {
+        if !plan.skip_if_exists {
+            return Err(eyre!(
+                "Destination already exists: {}",
+                plan.directory.display()
+            ));
+        }
+
+        // Already cloned (and presumably already has its upstream remote,
+        // if any) on a prior run; treat this run as a no-op success rather
+        // than re-adding a remote that's already there.
+        return Ok(());
+    }
+
+    let mut command = Command::new("jj");
+    command.arg("git").arg("clone");
+    if plan.colocate {
+        command.arg("--colocate");
+    }
+    command.arg(&plan.repo_url).arg(&plan.directory);
+    command.args(plan.rest);
+
+    if !command
+        .status()
+        .await
+        .context("Failed to execute jj cli")?
+        .success()
+    {
+        return Err(eyre!("JJ clone failed"));
+    }
+
+    if !plan.directory.exists() {
+        return Err(eyre!(
+            "jj reported success, but destination doesn't exist: {}",
+            plan.directory.display()
+        ));
+    }
+
+    let Some(upstream_url) = plan.upstream_url else {
+        return Ok(());
+    };
+
+    if !Command::new("jj")
+        .arg("-R")
+        .arg(&plan.directory)
+        .arg("git")
+        .arg("remote")
+        .arg("add")
+        .arg(plan.upstream_remote_name)
+        .arg(upstream_url)
+        .status()
+        .await
+        .context("Failed to execute jj cli")?
+        .success()
+    {
+        return Err(eyre!("Failed to add upstream remote"));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,377 @@
+use color_eyre::{
+    Result,
+    eyre::{Context, ContextCompat},
+};
+use octocrab::Octocrab;
+use serde::Deserialize;
+
+/// A repository-hosting service that `gh-jj` knows how to clone from.
+///
+/// Resolving a shorthand like `owner/repo` means asking the forge for the
+/// clone URL, falling back to the authenticated user when `owner` is
+/// omitted, and checking whether the repo is a fork so the parent can be
+/// wired up as the `upstream` remote.
+#[async_trait::async_trait]
+pub trait Forge {
+    /// The URL `jj git clone` should be pointed at for `owner/repo`.
+    fn clone_url(&self, owner: &str, repo: &str) -> String;
+
+    /// The authenticated user's login, used when no owner is given.
+    async fn default_owner(&self) -> Result<String>;
+
+    /// The parent repo's clone URL, if `owner/repo` is a fork.
+    async fn parent(&self, owner: &str, repo: &str) -> Result<Option<String>>;
+}
+
+/// GitHub, via `gh`'s stored credentials and octocrab.
+pub struct GitHub {
+    octocrab: Octocrab,
+}
+
+impl GitHub {
+    pub fn new() -> Result<Self> {
+        use gh_config::GITHUB_COM;
+
+        let hosts = gh_config::Hosts::load().context("Failed to get gh hosts")?;
+        let token = hosts
+            .retrieve_token(GITHUB_COM)
+            .context("Failed to retrieve github token")?
+            .context("No github.com token found")?;
+
+        Ok(Self {
+            octocrab: Octocrab::builder().personal_token(token).build()?,
+        })
+    }
+
+    /// The underlying octocrab client, for GitHub-specific operations (like
+    /// forking) that don't fit the `Forge` trait.
+    pub fn octocrab(&self) -> &Octocrab {
+        &self.octocrab
+    }
+}
+
+#[async_trait::async_trait]
+impl Forge for GitHub {
+    fn clone_url(&self, owner: &str, repo: &str) -> String {
+        format!("https://github.com/{owner}/{repo}")
+    }
+
+    async fn default_owner(&self) -> Result<String> {
+        use gh_config::GITHUB_COM;
+
+        let hosts = gh_config::Hosts::load().context("Failed to get gh hosts")?;
+        hosts
+            .get(GITHUB_COM)
+            .context("No github.com host found")?
+            .user
+            .clone()
+            .context("No github.com user found")
+    }
+
+    async fn parent(&self, owner: &str, repo: &str) -> Result<Option<String>> {
+        let repo = self
+            .octocrab
+            .repos(owner, repo)
+            .get()
+            .await
+            .context("Failed to get repo info")?;
+
+        Ok(repo
+            .parent
+            .and_then(|parent| parent.clone_url)
+            .map(|url| url.to_string()))
+    }
+}
+
+/// A Gitea or Forgejo instance, talked to directly over its REST API.
+///
+/// Forgejo and Gitea share an API shape (Forgejo is a Gitea fork), so one
+/// implementation covers both.
+pub struct Gitea {
+    base_url: String,
+    token: String,
+    client: reqwest::Client,
+}
+
+#[derive(Deserialize)]
+struct GiteaRepo {
+    parent: Option<GiteaParent>,
+}
+
+#[derive(Deserialize)]
+struct GiteaParent {
+    clone_url: String,
+}
+
+#[derive(Deserialize)]
+struct GiteaUser {
+    login: String,
+}
+
+impl Gitea {
+    pub fn new(base_url: String, token: String) -> Self {
+        Self {
+            base_url,
+            token,
+            client: reqwest::Client::new(),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Forge for Gitea {
+    fn clone_url(&self, owner: &str, repo: &str) -> String {
+        format!("{}/{owner}/{repo}.git", self.base_url)
+    }
+
+    async fn default_owner(&self) -> Result<String> {
+        let user: GiteaUser = self
+            .client
+            .get(format!("{}/api/v1/user", self.base_url))
+            .bearer_auth(&self.token)
+            .send()
+            .await
+            .context("Failed to reach Gitea/Forgejo instance")?
+            .error_for_status()
+            .context("Gitea/Forgejo rejected the request")?
+            .json()
+            .await
+            .context("Failed to parse Gitea/Forgejo user response")?;
+
+        Ok(user.login)
+    }
+
+    async fn parent(&self, owner: &str, repo: &str) -> Result<Option<String>> {
+        let repo: GiteaRepo = self
+            .client
+            .get(format!("{}/api/v1/repos/{owner}/{repo}", self.base_url))
+            .bearer_auth(&self.token)
+            .send()
+            .await
+            .context("Failed to get repo info from Gitea/Forgejo")?
+            .error_for_status()
+            .context("Gitea/Forgejo rejected the request")?
+            .json()
+            .await
+            .context("Failed to parse Gitea/Forgejo repo response")?;
+
+        Ok(repo.parent.map(|parent| parent.clone_url))
+    }
+}
+
+/// GitLab, talked to over its v4 REST API.
+pub struct GitLab {
+    base_url: String,
+    token: String,
+    client: reqwest::Client,
+}
+
+#[derive(Deserialize)]
+struct GitLabProject {
+    forked_from_project: Option<serde_json::Value>,
+}
+
+#[derive(Deserialize)]
+struct GitLabUser {
+    username: String,
+}
+
+impl GitLab {
+    pub fn new(base_url: String, token: String) -> Self {
+        Self {
+            base_url,
+            token,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    fn api(&self, path: &str) -> String {
+        format!("{}/api/v4/{path}", self.base_url)
+    }
+}
+
+#[async_trait::async_trait]
+impl Forge for GitLab {
+    fn clone_url(&self, owner: &str, repo: &str) -> String {
+        format!("{}/{owner}/{repo}.git", self.base_url)
+    }
+
+    async fn default_owner(&self) -> Result<String> {
+        let user: GitLabUser = self
+            .client
+            .get(self.api("user"))
+            .bearer_auth(&self.token)
+            .send()
+            .await
+            .context("Failed to reach GitLab instance")?
+            .error_for_status()
+            .context("GitLab rejected the request")?
+            .json()
+            .await
+            .context("Failed to parse GitLab user response")?;
+
+        Ok(user.username)
+    }
+
+    async fn parent(&self, owner: &str, repo: &str) -> Result<Option<String>> {
+        let project: GitLabProject = self
+            .client
+            .get(self.api(&format!("projects/{owner}%2F{repo}")))
+            .bearer_auth(&self.token)
+            .send()
+            .await
+            .context("Failed to get project info from GitLab")?
+            .error_for_status()
+            .context("GitLab rejected the request")?
+            .json()
+            .await
+            .context("Failed to parse GitLab project response")?;
+
+        let Some(parent) = project.forked_from_project else {
+            return Ok(None);
+        };
+
+        let parent_path = parent
+            .get("path_with_namespace")
+            .and_then(|v| v.as_str())
+            .context("GitLab fork info missing path_with_namespace")?;
+
+        Ok(Some(format!("{}/{parent_path}.git", self.base_url)))
+    }
+}
+
+/// Bitbucket Cloud, talked to over its 2.0 REST API.
+pub struct Bitbucket {
+    token: String,
+    client: reqwest::Client,
+}
+
+#[derive(Deserialize)]
+struct BitbucketRepo {
+    parent: Option<BitbucketParent>,
+}
+
+#[derive(Deserialize)]
+struct BitbucketParent {
+    links: BitbucketParentLinks,
+}
+
+#[derive(Deserialize)]
+struct BitbucketParentLinks {
+    html: BitbucketLink,
+}
+
+#[derive(Deserialize)]
+struct BitbucketLink {
+    href: String,
+}
+
+#[derive(Deserialize)]
+struct BitbucketUser {
+    username: String,
+}
+
+impl Bitbucket {
+    pub fn new(token: String) -> Self {
+        Self {
+            token,
+            client: reqwest::Client::new(),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Forge for Bitbucket {
+    fn clone_url(&self, owner: &str, repo: &str) -> String {
+        format!("https://bitbucket.org/{owner}/{repo}.git")
+    }
+
+    async fn default_owner(&self) -> Result<String> {
+        let user: BitbucketUser = self
+            .client
+            .get("https://api.bitbucket.org/2.0/user")
+            .bearer_auth(&self.token)
+            .send()
+            .await
+            .context("Failed to reach Bitbucket")?
+            .error_for_status()
+            .context("Bitbucket rejected the request")?
+            .json()
+            .await
+            .context("Failed to parse Bitbucket user response")?;
+
+        Ok(user.username)
+    }
+
+    async fn parent(&self, owner: &str, repo: &str) -> Result<Option<String>> {
+        let repo: BitbucketRepo = self
+            .client
+            .get(format!(
+                "https://api.bitbucket.org/2.0/repositories/{owner}/{repo}"
+            ))
+            .bearer_auth(&self.token)
+            .send()
+            .await
+            .context("Failed to get repo info from Bitbucket")?
+            .error_for_status()
+            .context("Bitbucket rejected the request")?
+            .json()
+            .await
+            .context("Failed to parse Bitbucket repo response")?;
+
+        Ok(repo.parent.map(|parent| format!("{}.git", parent.links.html.href)))
+    }
+}
+
+/// Which forge a `--forge` flag or a `host:owner/repo` shorthand selects.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum ForgeKind {
+    #[value(name = "github")]
+    GitHub,
+    #[value(name = "gitlab")]
+    GitLab,
+    #[value(name = "gitea", alias = "forgejo")]
+    Gitea,
+    #[value(name = "bitbucket")]
+    Bitbucket,
+}
+
+impl ForgeKind {
+    /// Resolves a `host:owner/repo` shorthand prefix to a forge kind and,
+    /// for prefixes that name a specific well-known instance (like
+    /// `codeberg`), its fixed host.
+    pub fn from_prefix(prefix: &str) -> Option<(Self, Option<&'static str>)> {
+        match prefix {
+            "github" => Some((ForgeKind::GitHub, None)),
+            "gitlab" => Some((ForgeKind::GitLab, None)),
+            "gitea" | "forgejo" => Some((ForgeKind::Gitea, None)),
+            "codeberg" => Some((ForgeKind::Gitea, Some("codeberg.org"))),
+            "bitbucket" => Some((ForgeKind::Bitbucket, None)),
+            _ => None,
+        }
+    }
+
+    /// Build the `Forge` for this kind, given an optional self-hosted host
+    /// name, which is served over `https://` (ignored for GitHub and
+    /// Bitbucket, which are single-tenant; GitLab defaults to `gitlab.com`).
+    pub fn build(self, host: Option<&str>) -> Result<Box<dyn Forge>> {
+        Ok(match self {
+            ForgeKind::GitHub => Box::new(GitHub::new()?),
+            ForgeKind::GitLab => {
+                let base_url = format!("https://{}", host.unwrap_or("gitlab.com"));
+                Box::new(GitLab::new(base_url, forge_token("GITLAB_TOKEN")?))
+            }
+            ForgeKind::Gitea => {
+                let host = host.context(
+                    "Self-hosted Gitea/Forgejo requires a host, e.g. `--forge gitea --host my.host owner/repo`",
+                )?;
+                Box::new(Gitea::new(format!("https://{host}"), forge_token("GITEA_TOKEN")?))
+            }
+            ForgeKind::Bitbucket => Box::new(Bitbucket::new(forge_token("BITBUCKET_TOKEN")?)),
+        })
+    }
+}
+
+fn forge_token(env_var: &str) -> Result<String> {
+    std::env::var(env_var).with_context(|| format!("Missing {env_var} environment variable"))
+}
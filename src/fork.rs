@@ -0,0 +1,124 @@
+use crate::clone::{ClonePlan, run_clone};
+use crate::forge::{Forge, GitHub};
+use crate::path::resolve_clone_directory;
+use clap::Parser;
+use color_eyre::{
+    Result,
+    eyre::{Context, ContextCompat, eyre},
+};
+use std::{path::PathBuf, time::Duration};
+
+#[derive(Parser)]
+/// Fork a GitHub repository, clone the fork, and wire up the original as upstream
+pub struct ForkCommand {
+    #[arg(value_name = "REPOSITORY")]
+    /// Repository to fork
+    ///
+    /// Must be given as `owner/repo`
+    repo: String,
+
+    #[arg()]
+    /// Directory into which to clone the fork
+    ///
+    /// By default, a new directory will be created in CWD with the name of the repo
+    directory: Option<PathBuf>,
+
+    #[arg(long)]
+    /// Colocate the repository (place `.git` in the root of the repository)
+    colocate: bool,
+
+    #[arg(short, long, default_value = "upstream")]
+    /// Upstream remote name for the forked-from repository
+    upstream_remote_name: String,
+
+    #[arg(long)]
+    /// If the destination directory already exists, skip cloning instead of erroring
+    skip_if_exists: bool,
+
+    #[arg(allow_hyphen_values = true, num_args = 0.., last = true)]
+    /// Arguments to pass to `jj git clone`
+    rest: Vec<String>,
+}
+
+pub async fn fork(cmd: ForkCommand) -> Result<()> {
+    let (owner, repo_name) = parse_shorthand(&cmd.repo)?;
+
+    let forge = GitHub::new()?;
+
+    // Forks always land under the authenticated user with the same repo
+    // name (barring a name clash), so check the likely destination before
+    // hitting the network at all. The authoritative check still happens
+    // in `run_clone` once the real fork owner/name are known.
+    let predicted_owner = forge.default_owner().await?;
+    let predicted_url = format!("https://github.com/{predicted_owner}/{repo_name}");
+    let predicted_directory = resolve_clone_directory(cmd.directory.clone(), None, &predicted_url)?;
+    if predicted_directory.exists() && !cmd.skip_if_exists {
+        return Err(eyre!(
+            "Destination already exists: {}",
+            predicted_directory.display()
+        ));
+    }
+
+    let octocrab = forge.octocrab();
+
+    let fork = octocrab
+        .repos(&owner, &repo_name)
+        .create_fork()
+        .send()
+        .await
+        .context("Failed to create fork")?;
+
+    let fork_owner = fork
+        .owner
+        .context("Forked repo is missing an owner")?
+        .login;
+    let fork_name = fork.name;
+
+    wait_for_fork(&forge, &fork_owner, &fork_name).await?;
+
+    let repo_url = format!("https://github.com/{fork_owner}/{fork_name}");
+    let directory = resolve_clone_directory(cmd.directory, None, &repo_url)?;
+
+    run_clone(ClonePlan {
+        repo_url,
+        upstream_url: Some(format!("https://github.com/{owner}/{repo_name}")),
+        directory,
+        colocate: cmd.colocate,
+        upstream_remote_name: cmd.upstream_remote_name,
+        skip_if_exists: cmd.skip_if_exists,
+        rest: cmd.rest,
+    })
+    .await
+}
+
+/// GitHub creates forks asynchronously, so newly-created ones 404 for a
+/// moment. Poll until the fork's repo info is fetchable.
+async fn wait_for_fork(forge: &GitHub, owner: &str, repo: &str) -> Result<()> {
+    const ATTEMPTS: u32 = 10;
+    const DELAY: Duration = Duration::from_secs(2);
+
+    for attempt in 1..=ATTEMPTS {
+        if forge.octocrab().repos(owner, repo).get().await.is_ok() {
+            return Ok(());
+        }
+        if attempt < ATTEMPTS {
+            tokio::time::sleep(DELAY).await;
+        }
+    }
+
+    Err(color_eyre::eyre::eyre!(
+        "Timed out waiting for fork {owner}/{repo} to become available"
+    ))
+}
+
+fn parse_shorthand(repo: &str) -> Result<(String, String)> {
+    let regex = regex::Regex::new(r"^([a-zA-Z0-9-]+)\/([a-zA-Z0-9_.-]+)$").unwrap();
+    let captures = regex
+        .captures(repo)
+        .context("Expected `owner/repo`, e.g. `octocat/Hello-World`")?;
+
+    Ok((
+        captures.get(1).unwrap().as_str().to_string(),
+        captures.get(2).unwrap().as_str().to_string(),
+    ))
+}
@@ -0,0 +1,72 @@
+use color_eyre::{
+    Result,
+    eyre::{Context, ContextCompat},
+};
+use regex::Regex;
+use std::path::PathBuf;
+
+/// Splits a clone URL into `(host, owner, repo)`.
+///
+/// Handles the `https://host/owner/repo[.git]`, `ssh://git@host/owner/repo`,
+/// and `git@host:owner/repo` forms.
+pub fn parse_url(url: &str) -> Result<(String, String, String)> {
+    let scheme_regex = Regex::new(r"^[a-z]+://(?:[^@/]+@)?([^/]+)/(.+)$").unwrap();
+    let scp_regex = Regex::new(r"^[^@/]+@([^:]+):(.+)$").unwrap();
+
+    let (host, path) = if let Some(captures) = scheme_regex.captures(url) {
+        (
+            captures.get(1).unwrap().as_str().to_string(),
+            captures.get(2).unwrap().as_str().to_string(),
+        )
+    } else if let Some(captures) = scp_regex.captures(url) {
+        (
+            captures.get(1).unwrap().as_str().to_string(),
+            captures.get(2).unwrap().as_str().to_string(),
+        )
+    } else {
+        return Err(color_eyre::eyre::eyre!("Couldn't parse clone URL: {url}"));
+    };
+
+    let path = path.strip_suffix(".git").unwrap_or(&path);
+    let (owner, repo) = path
+        .rsplit_once('/')
+        .context("Clone URL is missing an owner")?;
+
+    Ok((host, owner.to_string(), repo.to_string()))
+}
+
+/// Computes `<root>/<host>/<owner>/<repo>` for `repo_url`, creating the
+/// intermediate `<root>/<host>/<owner>` directories.
+pub fn rooted_directory(root: &std::path::Path, repo_url: &str) -> Result<PathBuf> {
+    let (host, owner, repo) = parse_url(repo_url)?;
+    let directory = root.join(host).join(owner).join(repo);
+
+    std::fs::create_dir_all(
+        directory
+            .parent()
+            .context("Computed clone directory has no parent")?,
+    )
+    .context("Failed to create root directory tree")?;
+
+    Ok(directory)
+}
+
+/// Deterministically computes where `repo_url` will be cloned to, without
+/// ever needing to parse jj's output: an explicit `directory` wins, then a
+/// `--root`-organized path, then `./{repo}` in the current directory.
+pub fn resolve_clone_directory(
+    directory: Option<PathBuf>,
+    root: Option<&std::path::Path>,
+    repo_url: &str,
+) -> Result<PathBuf> {
+    if let Some(directory) = directory {
+        return Ok(directory);
+    }
+
+    if let Some(root) = root {
+        return rooted_directory(root, repo_url);
+    }
+
+    let (_, _, repo) = parse_url(repo_url)?;
+    Ok(PathBuf::from(format!("./{repo}")))
+}
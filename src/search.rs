@@ -0,0 +1,179 @@
+use crate::forge::GitHub;
+use color_eyre::{Result, eyre::Context};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use indicatif::{ProgressBar, ProgressStyle};
+use ratatui::{
+    Terminal,
+    layout::{Constraint, Layout},
+    style::{Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+};
+use std::time::Duration;
+
+/// Fetches the authenticated user's repositories and lets them fuzzy-pick
+/// one interactively. Returns the selected repo's `owner/repo` shorthand.
+pub async fn pick_repo() -> Result<String> {
+    let repos = fetch_repos().await?;
+    run_picker(repos)
+}
+
+async fn fetch_repos() -> Result<Vec<String>> {
+    let spinner = ProgressBar::new_spinner();
+    spinner.set_style(
+        ProgressStyle::with_template("{spinner} {msg}")
+            .unwrap()
+            .tick_chars("⠋⠙⠹⠸⠼⠴⠦⠧⠇⠏"),
+    );
+    spinner.set_message("Fetching repositories...");
+    spinner.enable_steady_tick(Duration::from_millis(80));
+
+    let forge = GitHub::new()?;
+    let mut repos = Vec::new();
+    let mut page = forge
+        .octocrab()
+        .current()
+        .list_repos_for_authenticated_user()
+        .per_page(100)
+        .send()
+        .await
+        .context("Failed to list your repositories")?;
+
+    loop {
+        repos.extend(page.items.iter().map(|repo| repo.full_name.clone().unwrap_or_else(|| repo.name.clone())));
+
+        let Some(next) = forge
+            .octocrab()
+            .get_page(&page.next)
+            .await
+            .context("Failed to fetch next page of repositories")?
+        else {
+            break;
+        };
+        page = next;
+    }
+
+    spinner.finish_and_clear();
+
+    Ok(repos)
+}
+
+/// A subsequence fuzzy match: every character of `query` must appear in
+/// `candidate` in order, case-insensitively. Shorter gaps between matched
+/// characters score higher, so tighter matches sort first.
+fn fuzzy_score(query: &str, candidate: &str) -> Option<i64> {
+    if query.is_empty() {
+        return Some(0);
+    }
+
+    let candidate_lower = candidate.to_lowercase();
+    let mut chars = candidate_lower.char_indices();
+    let mut score = 0i64;
+    let mut last_match = None;
+
+    for q in query.to_lowercase().chars() {
+        let (index, _) = chars.by_ref().find(|&(_, c)| c == q)?;
+        if let Some(last) = last_match {
+            score -= (index as i64) - (last as i64);
+        }
+        last_match = Some(index);
+    }
+
+    Some(score)
+}
+
+fn run_picker(items: Vec<String>) -> Result<String> {
+    crossterm::terminal::enable_raw_mode().context("Failed to enable raw mode")?;
+    let mut stdout = std::io::stdout();
+    crossterm::execute!(stdout, crossterm::terminal::EnterAlternateScreen)
+        .context("Failed to enter alternate screen")?;
+    let backend = ratatui::backend::CrosstermBackend::new(stdout);
+    let mut terminal = Terminal::new(backend).context("Failed to start terminal")?;
+
+    let result = picker_loop(&mut terminal, &items);
+
+    crossterm::terminal::disable_raw_mode().context("Failed to disable raw mode")?;
+    crossterm::execute!(
+        terminal.backend_mut(),
+        crossterm::terminal::LeaveAlternateScreen
+    )
+    .context("Failed to leave alternate screen")?;
+
+    result
+}
+
+fn picker_loop<B: ratatui::backend::Backend>(
+    terminal: &mut Terminal<B>,
+    items: &[String],
+) -> Result<String> {
+    let mut query = String::new();
+    let mut state = ListState::default();
+    state.select(Some(0));
+
+    loop {
+        let mut matches: Vec<&String> = items
+            .iter()
+            .filter(|item| fuzzy_score(&query, item).is_some())
+            .collect();
+        matches.sort_by_key(|item| std::cmp::Reverse(fuzzy_score(&query, item).unwrap()));
+
+        let needs_reset = match state.selected() {
+            Some(i) => i >= matches.len(),
+            None => true,
+        };
+        if needs_reset {
+            state.select(if matches.is_empty() { None } else { Some(0) });
+        }
+
+        terminal
+            .draw(|frame| {
+                let [prompt_area, list_area] =
+                    Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(frame.area());
+
+                frame.render_widget(Paragraph::new(Line::from(format!("Search: {query}"))), prompt_area);
+
+                let list_items: Vec<ListItem> = matches.iter().map(|item| ListItem::new(item.as_str())).collect();
+                let list = List::new(list_items)
+                    .block(Block::default().borders(Borders::TOP).title("Repositories"))
+                    .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+
+                frame.render_stateful_widget(list, list_area, &mut state);
+            })
+            .context("Failed to draw picker")?;
+
+        if !event::poll(Duration::from_millis(100)).context("Failed to poll for input")? {
+            continue;
+        }
+
+        let Event::Key(key) = event::read().context("Failed to read input")? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+
+        match key.code {
+            KeyCode::Esc => {
+                return Err(color_eyre::eyre::eyre!("No repository selected"));
+            }
+            KeyCode::Enter => {
+                if let Some(selected) = state.selected().and_then(|i| matches.get(i)) {
+                    return Ok((*selected).clone());
+                }
+            }
+            KeyCode::Backspace => {
+                query.pop();
+            }
+            KeyCode::Up => {
+                state.select(state.selected().map(|i| i.saturating_sub(1)));
+            }
+            KeyCode::Down => {
+                state.select(state.selected().map(|i| (i + 1).min(matches.len().saturating_sub(1))));
+            }
+            KeyCode::Char(c) => {
+                query.push(c);
+            }
+            _ => {}
+        }
+    }
+}